@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::cancellation::CancellationToken;
+use crate::errors::CompilationUtilError;
+
+/// Runs `compile_one` over `items` using up to `max_concurrency` worker threads, returning one
+/// result per input in input order. One item failing does not stop the others from running.
+///
+/// Concurrency is bounded so that at most `max_concurrency` (at least one) subprocesses (or, for
+/// an in-process backend, compilations) are ever in flight at once, keeping the aggregate resource usage of a batch
+/// within a multiple of a single compilation's `ResourceLimits` budget rather than unbounded.
+///
+/// `cancellation_token` is checked before each item is picked up: once cancelled, in-flight items
+/// run to their own `compile_one` call's cancellation check (it is passed `cancellation_token`
+/// and is expected to honor it, as `compile_cancellable`/`compile_to_native_cancellable` do), and
+/// any item not yet started is resolved to `CompilationUtilError::Cancelled` without running it.
+pub fn run_batch<T, O>(
+    items: Vec<T>,
+    max_concurrency: usize,
+    cancellation_token: &CancellationToken,
+    compile_one: impl Fn(T, &CancellationToken) -> Result<O, CompilationUtilError> + Sync,
+) -> Vec<Result<O, CompilationUtilError>>
+where
+    T: Send,
+    O: Send,
+{
+    let item_count = items.len();
+    if item_count == 0 {
+        return Vec::new();
+    }
+
+    let queue: Mutex<VecDeque<(usize, T)>> = Mutex::new(items.into_iter().enumerate().collect());
+    let results: Mutex<Vec<Option<Result<O, CompilationUtilError>>>> =
+        Mutex::new((0..item_count).map(|_| None).collect());
+    let worker_count = max_concurrency.max(1).min(item_count);
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| loop {
+                if cancellation_token.is_cancelled() {
+                    let Some((index, _)) = queue.lock().unwrap().pop_front() else { break };
+                    results.lock().unwrap()[index] = Some(Err(CompilationUtilError::Cancelled));
+                    continue;
+                }
+                let Some((index, item)) = queue.lock().unwrap().pop_front() else { break };
+                let result = compile_one(item, cancellation_token);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every queued index is written by exactly one worker"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preserves_input_order() {
+        let items: Vec<usize> = (0..50).collect();
+        let results = run_batch(items, 8, &CancellationToken::new(), |item, _token| Ok(item * 2));
+
+        let expected: Vec<Result<usize, CompilationUtilError>> =
+            (0..50).map(|item| Ok(item * 2)).collect();
+        assert_eq!(format!("{results:?}"), format!("{expected:?}"));
+    }
+
+    #[test]
+    fn one_failure_does_not_abort_others() {
+        let items: Vec<usize> = (0..10).collect();
+        let results = run_batch(items, 4, &CancellationToken::new(), |item, _token| {
+            if item == 3 {
+                Err(CompilationUtilError::CompilationError("boom".to_owned()))
+            } else {
+                Ok(item)
+            }
+        });
+
+        assert_eq!(results.len(), 10);
+        for (index, result) in results.iter().enumerate() {
+            if index == 3 {
+                assert!(matches!(result, Err(CompilationUtilError::CompilationError(_))));
+            } else {
+                assert!(matches!(result, Ok(value) if *value == index));
+            }
+        }
+    }
+
+    #[test]
+    fn cancelled_token_resolves_all_items_to_cancelled() {
+        let cancellation_token = CancellationToken::new();
+        cancellation_token.cancel();
+
+        let items: Vec<usize> = (0..5).collect();
+        let results = run_batch(items, 2, &cancellation_token, |item, _token| Ok(item));
+
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|result| matches!(result, Err(CompilationUtilError::Cancelled))));
+    }
+
+    #[test]
+    fn empty_input_returns_empty_output() {
+        let results: Vec<Result<usize, CompilationUtilError>> =
+            run_batch(Vec::new(), 4, &CancellationToken::new(), |item, _token| Ok(item));
+        assert!(results.is_empty());
+    }
+}
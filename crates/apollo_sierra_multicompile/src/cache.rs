@@ -0,0 +1,268 @@
+use std::fs;
+#[cfg(feature = "cairo_native")]
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+#[cfg(feature = "cairo_native")]
+use cairo_native::executor::AotContractExecutor;
+use sha2::{Digest, Sha256};
+#[cfg(feature = "cairo_native")]
+use tempfile::NamedTempFile;
+use tracing::{debug, warn};
+
+use crate::cancellation::CancellationToken;
+use crate::config::ArtifactCacheConfig;
+use crate::errors::CompilationUtilError;
+use crate::SierraToCasmCompiler;
+#[cfg(feature = "cairo_native")]
+use crate::SierraToNativeCompiler;
+
+/// zstd compression level for cached native artifacts: high enough to meaningfully shrink
+/// shared-object output, without the much slower max levels that only pay off for long-lived
+/// archival data.
+#[cfg(feature = "cairo_native")]
+const ZSTD_COMPRESSION_LEVEL: i32 = 19;
+
+/// Content-addresses a Sierra `ContractClass` by hashing its serialized form, so the cache key
+/// doesn't depend on the caller having already computed a Starknet class hash.
+pub fn class_cache_key(contract_class: &ContractClass) -> Result<String, CompilationUtilError> {
+    let serialized = serde_json::to_vec(contract_class)?;
+    Ok(hex::encode(Sha256::digest(serialized)))
+}
+
+/// On-disk, class-hash-keyed cache of compiled artifacts.
+///
+/// CASM artifacts are stored uncompressed, re-serialized as compact JSON. Native `.so` artifacts
+/// are stored zstd-compressed: compiler output is highly compressible, and native caches
+/// otherwise tend to dominate disk usage. Compressed native artifacts are decompressed to a
+/// scratch temp file on read, since `AotContractExecutor::from_path` only loads from disk.
+#[derive(Clone)]
+pub struct ArtifactCache {
+    config: ArtifactCacheConfig,
+}
+
+impl ArtifactCache {
+    pub fn new(config: ArtifactCacheConfig) -> Result<Self, CompilationUtilError> {
+        fs::create_dir_all(&config.cache_dir)?;
+        Ok(Self { config })
+    }
+
+    fn casm_path(&self, key: &str) -> PathBuf {
+        self.config.cache_dir.join(format!("{key}.casm.json"))
+    }
+
+    #[cfg(feature = "cairo_native")]
+    fn native_path(&self, key: &str) -> PathBuf {
+        self.config.cache_dir.join(format!("{key}.native.zst"))
+    }
+
+    pub fn get_casm(&self, key: &str) -> Option<CasmContractClass> {
+        let path = self.casm_path(key);
+        let bytes = fs::read(&path).ok()?;
+        touch(&path);
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    pub fn put_casm(&self, key: &str, casm: &CasmContractClass) -> Result<(), CompilationUtilError> {
+        fs::write(self.casm_path(key), serde_json::to_vec(casm)?)?;
+        self.evict_if_needed()
+    }
+
+    #[cfg(feature = "cairo_native")]
+    pub fn get_native(&self, key: &str) -> Option<AotContractExecutor> {
+        let path = self.native_path(key);
+        let compressed = fs::read(&path).ok()?;
+        touch(&path);
+
+        let mut decompressed = Vec::new();
+        zstd::Decoder::new(compressed.as_slice()).ok()?.read_to_end(&mut decompressed).ok()?;
+
+        let decompressed_file = NamedTempFile::new().ok()?;
+        fs::write(decompressed_file.path(), &decompressed).ok()?;
+        AotContractExecutor::from_path(decompressed_file.path()).ok().flatten()
+    }
+
+    #[cfg(feature = "cairo_native")]
+    pub fn put_native(
+        &self,
+        key: &str,
+        executor: &mut AotContractExecutor,
+    ) -> Result<(), CompilationUtilError> {
+        let scratch_file = NamedTempFile::new()?;
+        executor.save(scratch_file.path())?;
+        let raw = fs::read(scratch_file.path())?;
+
+        // A large dictionary window pays off here: native objects compiled from similar Cairo
+        // sources share long runs of near-identical bytes (relocations, padding, stdlib code).
+        let compressed = zstd::encode_all(raw.as_slice(), ZSTD_COMPRESSION_LEVEL)?;
+        fs::write(self.native_path(key), compressed)?;
+        self.evict_if_needed()
+    }
+
+    /// Evicts the least-recently-used entries (by file modification time, which reads bump via
+    /// `touch`) until the cache is back
+    /// under `config.max_size_bytes`.
+    fn evict_if_needed(&self) -> Result<(), CompilationUtilError> {
+        let mut entries: Vec<(PathBuf, SystemTime, u64)> = fs::read_dir(&self.config.cache_dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((entry.path(), metadata.modified().ok()?, metadata.len()))
+            })
+            .collect();
+
+        let mut total_size: u64 = entries.iter().map(|(_, _, size)| size).sum();
+        if total_size <= self.config.max_size_bytes {
+            return Ok(());
+        }
+
+        entries.sort_by_key(|(_, last_used, _)| *last_used);
+        for (path, _, size) in entries {
+            if total_size <= self.config.max_size_bytes {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total_size = total_size.saturating_sub(size);
+                debug!("Evicted Sierra compilation cache entry {:?} (cache over size bound)", path);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Bumps a cache entry's modification time so size-based LRU eviction treats it as recently
+/// used; there is no separate access-time tracking to keep the cache a plain directory of files.
+fn touch(path: &Path) {
+    if let Ok(file) = fs::OpenOptions::new().write(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn write_with_mtime(path: &Path, contents: &[u8], mtime: SystemTime) {
+        fs::write(path, contents).unwrap();
+        let file = fs::OpenOptions::new().write(true).open(path).unwrap();
+        file.set_modified(mtime).unwrap();
+    }
+
+    #[test]
+    fn evict_if_needed_removes_least_recently_used_entries_first() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache = ArtifactCache::new(ArtifactCacheConfig {
+            cache_dir: cache_dir.path().to_path_buf(),
+            max_size_bytes: 15,
+        })
+        .unwrap();
+
+        let base = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        write_with_mtime(&cache.casm_path("oldest"), b"0123456789", base);
+        write_with_mtime(
+            &cache.casm_path("middle"),
+            b"0123456789",
+            base + Duration::from_secs(10),
+        );
+        write_with_mtime(
+            &cache.casm_path("newest"),
+            b"0123456789",
+            base + Duration::from_secs(20),
+        );
+
+        cache.evict_if_needed().unwrap();
+
+        assert!(!cache.casm_path("oldest").exists());
+        assert!(!cache.casm_path("middle").exists());
+        assert!(cache.casm_path("newest").exists());
+    }
+
+    #[test]
+    fn evict_if_needed_is_noop_under_budget() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache = ArtifactCache::new(ArtifactCacheConfig {
+            cache_dir: cache_dir.path().to_path_buf(),
+            max_size_bytes: 1_000,
+        })
+        .unwrap();
+
+        write_with_mtime(&cache.casm_path("only"), b"0123456789", SystemTime::now());
+        cache.evict_if_needed().unwrap();
+
+        assert!(cache.casm_path("only").exists());
+    }
+}
+
+/// Wraps a [`SierraToCasmCompiler`] (and, with `cairo_native`, a [`SierraToNativeCompiler`]) with
+/// an [`ArtifactCache`], so repeated compilations of the same contract class across block
+/// replays or sequencer restarts are served from disk instead of recompiled.
+#[derive(Clone)]
+pub struct CachingCompiler<C> {
+    inner: C,
+    cache: ArtifactCache,
+}
+
+impl<C> CachingCompiler<C> {
+    pub fn new(inner: C, config: ArtifactCacheConfig) -> Result<Self, CompilationUtilError> {
+        Ok(Self { inner, cache: ArtifactCache::new(config)? })
+    }
+}
+
+impl<C: SierraToCasmCompiler> SierraToCasmCompiler for CachingCompiler<C> {
+    fn compile_cancellable(
+        &self,
+        contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
+    ) -> Result<CasmContractClass, CompilationUtilError> {
+        let key = class_cache_key(&contract_class)?;
+        if let Some(cached) = self.cache.get_casm(&key) {
+            debug!("Sierra compilation cache hit for class {key}");
+            return Ok(cached);
+        }
+
+        let casm = self.inner.compile_cancellable(contract_class, cancellation_token)?;
+        if let Err(error) = self.cache.put_casm(&key, &casm) {
+            warn!("Failed to persist Sierra compilation artifact to cache: {error}");
+        }
+        Ok(casm)
+    }
+
+    fn max_concurrent_compilations(&self) -> usize {
+        self.inner.max_concurrent_compilations()
+    }
+}
+
+#[cfg(feature = "cairo_native")]
+impl<C: SierraToNativeCompiler> SierraToNativeCompiler for CachingCompiler<C> {
+    fn compile_to_native_cancellable(
+        &self,
+        contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
+    ) -> Result<AotContractExecutor, CompilationUtilError> {
+        let key = class_cache_key(&contract_class)?;
+        if let Some(cached) = self.cache.get_native(&key) {
+            debug!("Native compilation cache hit for class {key}");
+            return Ok(cached);
+        }
+
+        let mut executor =
+            self.inner.compile_to_native_cancellable(contract_class, cancellation_token)?;
+        if let Err(error) = self.cache.put_native(&key, &mut executor) {
+            warn!("Failed to persist native compilation artifact to cache: {error}");
+        }
+        Ok(executor)
+    }
+
+    fn panic_on_compilation_failure(&self) -> bool {
+        self.inner.panic_on_compilation_failure()
+    }
+
+    fn max_concurrent_compilations(&self) -> usize {
+        self.inner.max_concurrent_compilations()
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A cheap, cloneable flag a caller can use to ask an in-flight compilation to abort.
+///
+/// Checked cooperatively by the polling loop that
+/// [`crate::resource_limits::ResourceLimits::run`] supervises the compiler process with; it does
+/// not preempt the compiler process
+/// mid-instruction, but causes it to be killed (along with any children) and reaped promptly once
+/// observed, instead of being left to run to completion or leaking past a shutdown.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation. Idempotent.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
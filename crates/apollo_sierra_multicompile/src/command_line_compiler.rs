@@ -3,6 +3,7 @@ use std::io::Write;
 use std::os::unix::process::ExitStatusExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_lang_starknet_classes::contract_class::ContractClass;
@@ -11,10 +12,12 @@ use cairo_native::executor::AotContractExecutor;
 use tempfile::NamedTempFile;
 use tracing::info;
 
+use crate::cancellation::CancellationToken;
 use crate::config::SierraCompilationConfig;
 use crate::constants::CAIRO_LANG_BINARY_NAME;
 #[cfg(feature = "cairo_native")]
 use crate::constants::CAIRO_NATIVE_BINARY_NAME;
+use crate::discovery::discover_preferred_binary;
 use crate::errors::CompilationUtilError;
 use crate::paths::binary_path;
 use crate::resource_limits::ResourceLimits;
@@ -32,7 +35,15 @@ pub struct CommandLineCompiler {
 
 impl CommandLineCompiler {
     pub fn new(config: SierraCompilationConfig) -> Self {
-        let path_to_starknet_sierra_compile_binary = binary_path(out_dir(), CAIRO_LANG_BINARY_NAME);
+        let bundled_sierra_compile_binary = binary_path(out_dir(), CAIRO_LANG_BINARY_NAME);
+        let path_to_starknet_sierra_compile_binary = match &config.local_compiler_discovery {
+            Some(discovery_config) => discover_preferred_binary(
+                bundled_sierra_compile_binary,
+                CAIRO_LANG_BINARY_NAME,
+                discovery_config,
+            ),
+            None => bundled_sierra_compile_binary,
+        };
         info!("Using Sierra compiler binary at: {:?}", path_to_starknet_sierra_compile_binary);
 
         #[cfg(feature = "cairo_native")]
@@ -50,9 +61,10 @@ impl CommandLineCompiler {
 }
 
 impl SierraToCasmCompiler for CommandLineCompiler {
-    fn compile(
+    fn compile_cancellable(
         &self,
         contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
     ) -> Result<CasmContractClass, CompilationUtilError> {
         let compiler_binary_path = &self.path_to_starknet_sierra_compile_binary;
         let additional_args = &[
@@ -70,16 +82,24 @@ impl SierraToCasmCompiler for CommandLineCompiler {
             contract_class,
             additional_args,
             resource_limits,
+            Duration::from_secs(self.config.compilation_timeout_secs),
+            cancellation_token,
+            None,
         )?;
         Ok(serde_json::from_slice::<CasmContractClass>(&stdout)?)
     }
+
+    fn max_concurrent_compilations(&self) -> usize {
+        self.config.max_concurrent_compilations
+    }
 }
 
 #[cfg(feature = "cairo_native")]
 impl SierraToNativeCompiler for CommandLineCompiler {
-    fn compile_to_native(
+    fn compile_to_native_cancellable(
         &self,
         contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
     ) -> Result<AotContractExecutor, CompilationUtilError> {
         let compiler_binary_path = &self.path_to_starknet_native_compile_binary;
 
@@ -99,6 +119,9 @@ impl SierraToNativeCompiler for CommandLineCompiler {
             contract_class,
             &additional_args,
             resource_limits,
+            Duration::from_secs(self.config.compilation_timeout_secs),
+            cancellation_token,
+            Some(Path::new(output_file_path)),
         )?;
 
         Ok(AotContractExecutor::from_path(Path::new(&output_file_path))?.unwrap())
@@ -107,6 +130,10 @@ impl SierraToNativeCompiler for CommandLineCompiler {
     fn panic_on_compilation_failure(&self) -> bool {
         self.config.panic_on_compilation_failure
     }
+
+    fn max_concurrent_compilations(&self) -> usize {
+        self.config.max_concurrent_compilations
+    }
 }
 
 fn compile_with_args(
@@ -114,6 +141,9 @@ fn compile_with_args(
     contract_class: ContractClass,
     additional_args: &[&str],
     resource_limits: ResourceLimits,
+    timeout: Duration,
+    cancellation_token: &CancellationToken,
+    output_path: Option<&Path>,
 ) -> Result<Vec<u8>, CompilationUtilError> {
     // Create a temporary file to store the Sierra contract class.
     let serialized_contract_class = serde_json::to_string(&contract_class)?;
@@ -125,15 +155,17 @@ fn compile_with_args(
     ))?;
 
     // Set the parameters for the compile process.
-    // TODO(Arni, Avi): Setup the ulimit for the process.
     let mut command = Command::new(compiler_binary_path.as_os_str());
     command.arg(temp_file_path).args(additional_args);
 
-    // Apply the resource limits to the command.
-    resource_limits.apply(&mut command);
-
-    // Run the compile process.
-    let compile_output = command.output()?;
+    // Run the compile process under the resource limits, a wall-clock timeout, and the given
+    // cancellation token. On unix the limits are applied to `command` before it is spawned (via
+    // `ulimit`); on Windows the spawned child is assigned to a Job Object enforcing them, since
+    // there is no equivalent pre-exec hook there. Either way, a deadline or cancellation kills
+    // the whole process tree and reaps it before returning, so `temp_file` (and any native
+    // output file the caller is writing to) are cleaned up on every path, not just success.
+    let compile_output =
+        resource_limits.run(&mut command, timeout, cancellation_token, output_path)?;
 
     if !compile_output.status.success() {
         #[cfg(target_family = "unix")]
@@ -150,6 +182,9 @@ fn compile_with_args(
             Some(sig) => &format!("Process terminated by unexpected signal: {}", sig),
         };
 
+        // On Windows, `ResourceLimits::run` already turns a Job Object termination into a
+        // `CompilationError` before returning a non-success output, so a generic status is all
+        // that is left to report here.
         #[cfg(not(target_family = "unix"))]
         let signal_info = "Process exited with non-zero status";
 
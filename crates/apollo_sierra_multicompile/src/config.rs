@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::CachingCompiler;
+use crate::command_line_compiler::CommandLineCompiler;
+use crate::errors::CompilationUtilError;
+use crate::in_process_compiler::InProcessCompiler;
+use crate::SierraToCasmCompiler;
+#[cfg(feature = "cairo_native")]
+use crate::SierraToNativeCompiler;
+
+/// Which implementation is used to turn Sierra contract classes into CASM (and, when the
+/// `cairo_native` feature is on, into native executables).
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SierraCompilationBackend {
+    /// Spawn the compiler as a subprocess, isolated via [`crate::resource_limits::ResourceLimits`].
+    /// Slower (serialization + process spawn per class), but a misbehaving compilation cannot
+    /// bring down the caller.
+    #[default]
+    CommandLine,
+    /// Compile in-process via `cairo-lang-starknet-classes`, skipping the filesystem and the
+    /// subprocess round-trip. Faster, but shares fate with the caller's process.
+    InProcess,
+}
+
+/// Bounds and eviction policy for [`crate::cache::ArtifactCache`], the on-disk, class-hash-keyed
+/// cache of compiled CASM/native artifacts.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArtifactCacheConfig {
+    /// Directory compiled artifacts are cached under. Created on first use if missing.
+    pub cache_dir: PathBuf,
+    /// Soft cap on total cache size, in bytes. Once exceeded, the least-recently-used entries
+    /// (by file modification time, bumped on each cache hit) are evicted until the cache is back
+    /// under this bound.
+    pub max_size_bytes: u64,
+}
+
+impl Default for ArtifactCacheConfig {
+    fn default() -> Self {
+        Self {
+            cache_dir: std::env::temp_dir().join("sierra_compilation_cache"),
+            max_size_bytes: 1_000_000_000,
+        }
+    }
+}
+
+/// Configures preferring a locally installed compiler binary over the one bundled with this
+/// crate. See [`crate::discovery::discover_preferred_binary`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LocalCompilerDiscoveryConfig {
+    /// Explicit path to a locally installed compiler binary. When unset, `PATH` is searched for
+    /// a binary named after `constants::CAIRO_LANG_BINARY_NAME`.
+    pub path_override: Option<PathBuf>,
+    /// Version range (`semver::VersionReq` syntax) a discovered binary's `--version` output must
+    /// satisfy to be adopted; otherwise the bundled binary is used.
+    pub required_version: String,
+}
+
+impl Default for LocalCompilerDiscoveryConfig {
+    fn default() -> Self {
+        Self { path_override: None, required_version: "*".to_owned() }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SierraCompilationConfig {
+    /// Selects the compilation backend that [`Self::build_casm_compiler`] /
+    /// [`Self::build_native_compiler`] construct.
+    pub backend: SierraCompilationBackend,
+    /// When set, a locally installed Sierra compiler binary is preferred over the bundled one,
+    /// subject to a version check.
+    pub local_compiler_discovery: Option<LocalCompilerDiscoveryConfig>,
+    /// When set, compiled artifacts are memoized on disk, keyed by a content hash of the Sierra
+    /// contract class, so identical classes (e.g. re-declared across block replays) are not
+    /// recompiled. See [`crate::cache::CachingCompiler`].
+    pub cache: Option<ArtifactCacheConfig>,
+    pub max_casm_bytecode_size: usize,
+    pub max_native_bytecode_size: usize,
+    /// Max CPU time (in seconds) the compiler subprocess may consume.
+    pub max_cpu_time: u64,
+    /// Max memory (in bytes) the compiler subprocess may consume.
+    pub max_memory_usage: u64,
+    /// Wall-clock bound on a single compilation, enforced regardless of platform (unlike the CPU
+    /// `ulimit`, which unix applies but Windows has no equivalent of). Exceeding it kills the
+    /// compiler process (and its children) and surfaces `CompilationUtilError::Timeout`.
+    pub compilation_timeout_secs: u64,
+    /// Upper bound on how many classes `compile_batch`/`compile_to_native_batch` compile
+    /// concurrently. Bounds aggregate CPU/memory usage to roughly this many times a single
+    /// compilation's `ResourceLimits` budget, rather than letting a large batch spawn unbounded
+    /// concurrent compiler subprocesses.
+    pub max_concurrent_compilations: usize,
+    pub optimization_level: u8,
+    pub panic_on_compilation_failure: bool,
+    /// Path to a locally built `starknet-native-compile` binary. Falls back to the one bundled
+    /// at `OUT_DIR` when unset.
+    pub sierra_to_native_compiler_path: Option<PathBuf>,
+}
+
+impl Default for SierraCompilationConfig {
+    fn default() -> Self {
+        Self {
+            backend: SierraCompilationBackend::default(),
+            local_compiler_discovery: None,
+            cache: None,
+            max_casm_bytecode_size: 180_000,
+            max_native_bytecode_size: 500_000,
+            max_cpu_time: 20,
+            max_memory_usage: 5_000_000_000,
+            compilation_timeout_secs: 30,
+            max_concurrent_compilations: 4,
+            optimization_level: 0,
+            panic_on_compilation_failure: false,
+            sierra_to_native_compiler_path: None,
+        }
+    }
+}
+
+impl SierraCompilationConfig {
+    /// Builds the CASM compiler selected by [`Self::backend`], wrapped in a
+    /// [`CachingCompiler`] when [`Self::cache`] is set.
+    pub fn build_casm_compiler(&self) -> Result<Box<dyn SierraToCasmCompiler>, CompilationUtilError> {
+        let backend: Box<dyn SierraToCasmCompiler> = match self.backend {
+            SierraCompilationBackend::CommandLine => {
+                Box::new(CommandLineCompiler::new(self.clone()))
+            }
+            SierraCompilationBackend::InProcess => Box::new(InProcessCompiler::new(self.clone())),
+        };
+        match &self.cache {
+            Some(cache_config) => Ok(Box::new(CachingCompiler::new(backend, cache_config.clone())?)),
+            None => Ok(backend),
+        }
+    }
+
+    /// Builds the native compiler selected by [`Self::backend`], wrapped in a
+    /// [`CachingCompiler`] when [`Self::cache`] is set.
+    #[cfg(feature = "cairo_native")]
+    pub fn build_native_compiler(
+        &self,
+    ) -> Result<Box<dyn SierraToNativeCompiler>, CompilationUtilError> {
+        let backend: Box<dyn SierraToNativeCompiler> = match self.backend {
+            SierraCompilationBackend::CommandLine => {
+                Box::new(CommandLineCompiler::new(self.clone()))
+            }
+            SierraCompilationBackend::InProcess => Box::new(InProcessCompiler::new(self.clone())),
+        };
+        match &self.cache {
+            Some(cache_config) => Ok(Box::new(CachingCompiler::new(backend, cache_config.clone())?)),
+            None => Ok(backend),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub const CAIRO_LANG_BINARY_NAME: &str = "starknet-sierra-compile";
+#[cfg(feature = "cairo_native")]
+pub const CAIRO_NATIVE_BINARY_NAME: &str = "starknet-native-compile";
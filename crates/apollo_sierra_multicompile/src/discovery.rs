@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use semver::{Version, VersionReq};
+use tracing::{info, warn};
+
+use crate::config::LocalCompilerDiscoveryConfig;
+use crate::errors::CompilationUtilError;
+
+/// Looks for a locally installed compiler binary (named `binary_name`, found via
+/// `discovery_config.path_override` or on `PATH`) and, if it satisfies
+/// `discovery_config.required_version`, returns its path in preference to `bundled_path`.
+///
+/// Falls back to `bundled_path` both when no local binary is found and when one is found but
+/// fails the version query or does not satisfy the required range -- the latter case logs a
+/// warning so the mismatch isn't silent, even though compilation proceeds with the bundled
+/// binary rather than failing outright.
+pub fn discover_preferred_binary(
+    bundled_path: PathBuf,
+    binary_name: &str,
+    discovery_config: &LocalCompilerDiscoveryConfig,
+) -> PathBuf {
+    let Some(candidate) = locate_binary(binary_name, discovery_config) else {
+        return bundled_path;
+    };
+
+    match check_version(&candidate, &discovery_config.required_version) {
+        Ok(()) => {
+            info!(
+                "Using locally installed compiler binary at {candidate:?} in preference to the \
+                 bundled one"
+            );
+            candidate
+        }
+        Err(error) => {
+            warn!(
+                "Ignoring locally installed compiler binary at {candidate:?}, falling back to \
+                 the bundled one: {error}"
+            );
+            bundled_path
+        }
+    }
+}
+
+fn locate_binary(
+    binary_name: &str,
+    discovery_config: &LocalCompilerDiscoveryConfig,
+) -> Option<PathBuf> {
+    if let Some(path_override) = &discovery_config.path_override {
+        return Some(path_override.clone());
+    }
+    which::which(binary_name).ok()
+}
+
+/// Runs `binary_path --version` and checks the reported version against `required_version`.
+fn check_version(binary_path: &Path, required_version: &str) -> Result<(), CompilationUtilError> {
+    let required_version = VersionReq::parse(required_version).map_err(|error| {
+        CompilationUtilError::UnexpectedError(format!(
+            "Invalid required compiler version range {required_version:?}: {error}"
+        ))
+    })?;
+
+    let output = Command::new(binary_path).arg("--version").output()?;
+    if !output.status.success() {
+        return Err(CompilationUtilError::CompilationError(format!(
+            "Version query failed for {binary_path:?}"
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = parse_version_from_output(&stdout, binary_path)?;
+    check_required_version(&version, &required_version, binary_path)
+}
+
+/// Extracts the first whitespace-separated, digit-led token from a `--version` banner and parses
+/// it as a semver version.
+fn parse_version_from_output(
+    stdout: &str,
+    binary_path: &Path,
+) -> Result<Version, CompilationUtilError> {
+    let version_token = stdout
+        .split_whitespace()
+        .find(|token| token.chars().next().is_some_and(|c| c.is_ascii_digit()))
+        .ok_or_else(|| {
+            CompilationUtilError::CompilationError(format!(
+                "Could not find a version number in {binary_path:?} --version output: \
+                 {stdout:?}"
+            ))
+        })?;
+    Version::parse(version_token).map_err(|error| {
+        CompilationUtilError::CompilationError(format!(
+            "Could not parse version {version_token:?} reported by {binary_path:?}: {error}"
+        ))
+    })
+}
+
+fn check_required_version(
+    version: &Version,
+    required_version: &VersionReq,
+    binary_path: &Path,
+) -> Result<(), CompilationUtilError> {
+    if required_version.matches(version) {
+        Ok(())
+    } else {
+        Err(CompilationUtilError::CompilationError(format!(
+            "{binary_path:?} reports version {version}, which does not satisfy the required \
+             range {required_version}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_binary_path() -> PathBuf {
+        PathBuf::from("/usr/bin/fake-sierra-compile")
+    }
+
+    #[test]
+    fn parse_version_from_output_extracts_first_digit_led_token() {
+        let version =
+            parse_version_from_output("starknet-sierra-compile 2.7.1\n", &fake_binary_path())
+                .unwrap();
+        assert_eq!(version, Version::new(2, 7, 1));
+    }
+
+    #[test]
+    fn parse_version_from_output_errors_without_a_digit_led_token() {
+        let error =
+            parse_version_from_output("no version number here\n", &fake_binary_path()).unwrap_err();
+        assert!(matches!(error, CompilationUtilError::CompilationError(_)));
+    }
+
+    #[test]
+    fn parse_version_from_output_errors_on_unparsable_version_token() {
+        let error =
+            parse_version_from_output("version 2.x.y\n", &fake_binary_path()).unwrap_err();
+        assert!(matches!(error, CompilationUtilError::CompilationError(_)));
+    }
+
+    #[test]
+    fn check_required_version_accepts_a_version_in_range() {
+        let version = Version::new(2, 7, 1);
+        let required_version = VersionReq::parse("^2").unwrap();
+        assert!(check_required_version(&version, &required_version, &fake_binary_path()).is_ok());
+    }
+
+    #[test]
+    fn check_required_version_rejects_a_version_out_of_range() {
+        let version = Version::new(1, 0, 0);
+        let required_version = VersionReq::parse("^2").unwrap();
+        let error =
+            check_required_version(&version, &required_version, &fake_binary_path()).unwrap_err();
+        assert!(matches!(error, CompilationUtilError::CompilationError(_)));
+    }
+}
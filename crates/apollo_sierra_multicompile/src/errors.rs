@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CompilationUtilError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    SerdeError(#[from] serde_json::Error),
+    #[cfg(feature = "cairo_native")]
+    #[error(transparent)]
+    NativeError(#[from] cairo_native::error::Error),
+    #[error("Compilation error: {0}")]
+    CompilationError(String),
+    #[error("Unexpected error: {0}")]
+    UnexpectedError(String),
+    #[error("Compilation timed out")]
+    Timeout,
+    #[error("Compilation was cancelled")]
+    Cancelled,
+}
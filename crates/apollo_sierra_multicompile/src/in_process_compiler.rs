@@ -0,0 +1,88 @@
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+#[cfg(feature = "cairo_native")]
+use cairo_native::context::NativeContext;
+#[cfg(feature = "cairo_native")]
+use cairo_native::executor::AotContractExecutor;
+
+use crate::cancellation::CancellationToken;
+use crate::config::SierraCompilationConfig;
+use crate::errors::CompilationUtilError;
+use crate::SierraToCasmCompiler;
+#[cfg(feature = "cairo_native")]
+use crate::SierraToNativeCompiler;
+
+/// Compiles Sierra contract classes by calling `cairo-lang-starknet-classes` (and, with the
+/// `cairo_native` feature, `cairo_native`) directly in-process, rather than going through
+/// [`crate::command_line_compiler::CommandLineCompiler`]'s subprocess + temp-file round-trip.
+///
+/// This trades away the process isolation of [`CommandLineCompiler`] for lower latency; prefer
+/// it when the caller already trusts the contract classes it compiles (e.g. classes that were
+/// already validated on-chain).
+#[derive(Clone)]
+pub struct InProcessCompiler {
+    pub config: SierraCompilationConfig,
+}
+
+impl InProcessCompiler {
+    pub fn new(config: SierraCompilationConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl SierraToCasmCompiler for InProcessCompiler {
+    fn compile_cancellable(
+        &self,
+        contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
+    ) -> Result<CasmContractClass, CompilationUtilError> {
+        // There is no natural mid-compilation point to check the token from a single library
+        // call, so cancellation is only observed before starting.
+        if cancellation_token.is_cancelled() {
+            return Err(CompilationUtilError::Cancelled);
+        }
+        let add_pythonic_hints = true;
+        CasmContractClass::from_contract_class(
+            contract_class,
+            add_pythonic_hints,
+            self.config.max_casm_bytecode_size,
+        )
+        .map_err(|error| CompilationUtilError::CompilationError(error.to_string()))
+    }
+
+    fn max_concurrent_compilations(&self) -> usize {
+        self.config.max_concurrent_compilations
+    }
+}
+
+#[cfg(feature = "cairo_native")]
+impl SierraToNativeCompiler for InProcessCompiler {
+    fn compile_to_native_cancellable(
+        &self,
+        contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
+    ) -> Result<AotContractExecutor, CompilationUtilError> {
+        if cancellation_token.is_cancelled() {
+            return Err(CompilationUtilError::Cancelled);
+        }
+        let native_context = NativeContext::new();
+        let sierra_program = contract_class.extract_sierra_program().map_err(|error| {
+            CompilationUtilError::CompilationError(error.to_string())
+        })?;
+        let native_module = native_context
+            .compile(&sierra_program, false)
+            .map_err(|error| CompilationUtilError::CompilationError(error.to_string()))?;
+
+        let executor =
+            AotContractExecutor::new(&native_module, self.config.optimization_level.into())?;
+        Ok(executor)
+    }
+
+    fn panic_on_compilation_failure(&self) -> bool {
+        self.config.panic_on_compilation_failure
+    }
+
+    fn max_concurrent_compilations(&self) -> usize {
+        self.config.max_concurrent_compilations
+    }
+}
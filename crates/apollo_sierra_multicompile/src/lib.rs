@@ -0,0 +1,170 @@
+pub mod batch;
+pub mod cache;
+pub mod cancellation;
+pub mod command_line_compiler;
+pub mod config;
+pub mod constants;
+pub mod discovery;
+pub mod errors;
+pub mod in_process_compiler;
+pub mod paths;
+pub mod resource_limits;
+
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+#[cfg(feature = "cairo_native")]
+use cairo_native::executor::AotContractExecutor;
+
+use crate::cancellation::CancellationToken;
+use crate::errors::CompilationUtilError;
+
+/// Compiles a Sierra contract class into a CASM contract class.
+pub trait SierraToCasmCompiler: Send + Sync {
+    /// Compiles `contract_class` with no way to cancel once started. Equivalent to
+    /// [`Self::compile_cancellable`] with a token that is never cancelled.
+    fn compile(
+        &self,
+        contract_class: ContractClass,
+    ) -> Result<CasmContractClass, CompilationUtilError> {
+        self.compile_cancellable(contract_class, &CancellationToken::new())
+    }
+
+    /// Compiles `contract_class`, aborting with `CompilationUtilError::Cancelled` if
+    /// `cancellation_token` is cancelled before compilation finishes. Backends that cannot
+    /// observe cancellation mid-compilation (e.g. in-process ones) only check it before starting.
+    fn compile_cancellable(
+        &self,
+        contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
+    ) -> Result<CasmContractClass, CompilationUtilError>;
+
+    /// Upper bound on how many `compile` calls `compile_batch` runs concurrently. Backends that
+    /// spawn a subprocess per class should read this from their own configuration; the default
+    /// serializes (one class at a time).
+    fn max_concurrent_compilations(&self) -> usize {
+        1
+    }
+
+    /// Compiles many classes, fanning out across up to [`Self::max_concurrent_compilations`]
+    /// worker threads, sharing the resource budget each individual `compile` call already
+    /// applies. Returns one result per input, in input order; one class failing to compile does
+    /// not abort the others. Equivalent to [`Self::compile_batch_cancellable`] with a token that
+    /// is never cancelled.
+    fn compile_batch(
+        &self,
+        contract_classes: Vec<ContractClass>,
+    ) -> Vec<Result<CasmContractClass, CompilationUtilError>> {
+        self.compile_batch_cancellable(contract_classes, &CancellationToken::new())
+    }
+
+    /// Compiles many classes like [`Self::compile_batch`], but a cancelled `cancellation_token`
+    /// aborts in-flight compilations and resolves any not-yet-started class to
+    /// `CompilationUtilError::Cancelled`, so a shutting-down caller can abort a whole batch
+    /// instead of waiting for it to run to completion.
+    fn compile_batch_cancellable(
+        &self,
+        contract_classes: Vec<ContractClass>,
+        cancellation_token: &CancellationToken,
+    ) -> Vec<Result<CasmContractClass, CompilationUtilError>> {
+        crate::batch::run_batch(
+            contract_classes,
+            self.max_concurrent_compilations(),
+            cancellation_token,
+            |class, token| self.compile_cancellable(class, token),
+        )
+    }
+}
+
+impl SierraToCasmCompiler for Box<dyn SierraToCasmCompiler> {
+    fn compile_cancellable(
+        &self,
+        contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
+    ) -> Result<CasmContractClass, CompilationUtilError> {
+        (**self).compile_cancellable(contract_class, cancellation_token)
+    }
+
+    fn max_concurrent_compilations(&self) -> usize {
+        (**self).max_concurrent_compilations()
+    }
+}
+
+/// Compiles a Sierra contract class into a native executable.
+#[cfg(feature = "cairo_native")]
+pub trait SierraToNativeCompiler: Send + Sync {
+    /// Compiles `contract_class` with no way to cancel once started. Equivalent to
+    /// [`Self::compile_to_native_cancellable`] with a token that is never cancelled.
+    fn compile_to_native(
+        &self,
+        contract_class: ContractClass,
+    ) -> Result<AotContractExecutor, CompilationUtilError> {
+        self.compile_to_native_cancellable(contract_class, &CancellationToken::new())
+    }
+
+    /// Compiles `contract_class` to a native executable, aborting with
+    /// `CompilationUtilError::Cancelled` if `cancellation_token` is cancelled before compilation
+    /// finishes.
+    fn compile_to_native_cancellable(
+        &self,
+        contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
+    ) -> Result<AotContractExecutor, CompilationUtilError>;
+
+    /// Whether a compilation failure should panic rather than be reported as an error. Native
+    /// compilation is best-effort in some deployments, so this is configurable.
+    fn panic_on_compilation_failure(&self) -> bool;
+
+    /// Upper bound on how many `compile_to_native` calls `compile_to_native_batch` runs
+    /// concurrently. Backends that spawn a subprocess per class should read this from their own
+    /// configuration; the default serializes (one class at a time).
+    fn max_concurrent_compilations(&self) -> usize {
+        1
+    }
+
+    /// Compiles many classes to native executables, fanning out across up to
+    /// [`Self::max_concurrent_compilations`] worker threads. Returns one result per input, in
+    /// input order; one class failing to compile does not abort the others. Equivalent to
+    /// [`Self::compile_to_native_batch_cancellable`] with a token that is never cancelled.
+    fn compile_to_native_batch(
+        &self,
+        contract_classes: Vec<ContractClass>,
+    ) -> Vec<Result<AotContractExecutor, CompilationUtilError>> {
+        self.compile_to_native_batch_cancellable(contract_classes, &CancellationToken::new())
+    }
+
+    /// Compiles many classes like [`Self::compile_to_native_batch`], but a cancelled
+    /// `cancellation_token` aborts in-flight compilations and resolves any not-yet-started class
+    /// to `CompilationUtilError::Cancelled`, so a shutting-down caller can abort a whole batch
+    /// instead of waiting for it to run to completion.
+    fn compile_to_native_batch_cancellable(
+        &self,
+        contract_classes: Vec<ContractClass>,
+        cancellation_token: &CancellationToken,
+    ) -> Vec<Result<AotContractExecutor, CompilationUtilError>> {
+        crate::batch::run_batch(
+            contract_classes,
+            self.max_concurrent_compilations(),
+            cancellation_token,
+            |class, token| self.compile_to_native_cancellable(class, token),
+        )
+    }
+}
+
+#[cfg(feature = "cairo_native")]
+impl SierraToNativeCompiler for Box<dyn SierraToNativeCompiler> {
+    fn compile_to_native_cancellable(
+        &self,
+        contract_class: ContractClass,
+        cancellation_token: &CancellationToken,
+    ) -> Result<AotContractExecutor, CompilationUtilError> {
+        (**self).compile_to_native_cancellable(contract_class, cancellation_token)
+    }
+
+    fn panic_on_compilation_failure(&self) -> bool {
+        (**self).panic_on_compilation_failure()
+    }
+
+    fn max_concurrent_compilations(&self) -> usize {
+        (**self).max_concurrent_compilations()
+    }
+}
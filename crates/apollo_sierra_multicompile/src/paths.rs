@@ -0,0 +1,7 @@
+use std::path::PathBuf;
+
+/// Resolves the path to a baked-in compiler binary, built alongside this crate by `build.rs`
+/// and copied into `OUT_DIR`.
+pub fn binary_path(out_dir: PathBuf, binary_name: &str) -> PathBuf {
+    out_dir.join(binary_name)
+}
@@ -0,0 +1,395 @@
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::{Duration, Instant};
+
+use crate::cancellation::CancellationToken;
+use crate::errors::CompilationUtilError;
+
+/// CPU time limit, in seconds.
+const CPU_TIME_RESOURCE_FLAG_NAME: &str = "t";
+/// Memory usage limit, in bytes (handed to `ulimit -v` in KiB).
+const MEMORY_USAGE_RESOURCE_FLAG_NAME: &str = "v";
+/// Output file size limit, in bytes (handed to `ulimit -f` in 512-byte blocks); used to bound the
+/// size of the native `.so` artifact.
+const FILE_SIZE_RESOURCE_FLAG_NAME: &str = "f";
+
+/// How often the supervising thread polls the child for exit and checks the deadline/
+/// cancellation token. Short enough that a cancellation or timeout is acted on promptly, long
+/// enough not to busy-loop.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Resource limits applied to the spawned compiler child process, to bound the damage a
+/// malicious or buggy Sierra program can do on the host.
+#[derive(Clone, Debug, Default)]
+pub struct ResourceLimits {
+    max_cpu_time: Option<u64>,
+    max_file_size: Option<u64>,
+    max_memory_usage: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn new(
+        max_cpu_time: Option<u64>,
+        max_file_size: Option<u64>,
+        max_memory_usage: Option<u64>,
+    ) -> Self {
+        Self { max_cpu_time, max_file_size, max_memory_usage }
+    }
+
+    /// Applies the configured limits to the given command, to be enforced once it is spawned.
+    #[cfg(target_family = "unix")]
+    fn apply(&self, command: &mut Command) {
+        let mut prefix_args = Vec::new();
+        if let Some(max_cpu_time) = self.max_cpu_time {
+            prefix_args.push(format!("ulimit -{CPU_TIME_RESOURCE_FLAG_NAME} {max_cpu_time}"));
+        }
+        if let Some(max_file_size) = self.max_file_size {
+            let blocks = max_file_size / 512;
+            prefix_args.push(format!("ulimit -{FILE_SIZE_RESOURCE_FLAG_NAME} {blocks}"));
+        }
+        if let Some(max_memory_usage) = self.max_memory_usage {
+            let kibibytes = max_memory_usage / 1024;
+            prefix_args.push(format!("ulimit -{MEMORY_USAGE_RESOURCE_FLAG_NAME} {kibibytes}"));
+        }
+        if prefix_args.is_empty() {
+            return;
+        }
+
+        // Re-exec the command through `ulimit`, applied in the child's shell before the real
+        // binary takes over. This keeps `ResourceLimits` a thin, testable wrapper instead of
+        // requiring unsafe `pre_exec` hooks.
+        let original_program = command.get_program().to_owned();
+        let original_args: Vec<_> = command.get_args().map(|arg| arg.to_owned()).collect();
+        // One `ulimit` call per resource: POSIX `sh` sets a single limit per invocation.
+        let ulimit_cmd = format!("{}; exec \"$0\" \"$@\"", prefix_args.join("; "));
+        *command = Command::new("sh");
+        command.arg("-c").arg(ulimit_cmd).arg(original_program).args(original_args);
+    }
+
+    /// Spawning under these limits is unix-only (`apply` re-execs via `ulimit` before the
+    /// compiler binary takes over); on Windows there is no equivalent pre-exec hook, so the
+    /// limits are instead enforced post-spawn by a Job Object (see `windows_job`).
+    #[cfg(not(target_family = "unix"))]
+    fn apply(&self, _command: &mut Command) {}
+
+    /// Runs `command` to completion under these resource limits, a wall-clock `timeout`, and a
+    /// cooperative `cancellation_token`, returning its output.
+    ///
+    /// If the deadline passes or the token is cancelled first, the child (and any children it
+    /// spawned) is killed and reaped before this returns
+    /// `CompilationUtilError::Timeout`/`Cancelled`. On unix, a limit violation is returned as the
+    /// child's non-success `Output`, whose signal is decoded by
+    /// [`crate::command_line_compiler::compile_with_args`]; on Windows, a Job Object limit
+    /// violation is translated here into a `CompilationUtilError::CompilationError` analogous to
+    /// that unix signal decoding.
+    ///
+    /// `output_path`, when given, is the file the child is expected to have written its output to
+    /// (e.g. the native `.so` artifact); once the child exits successfully, it is checked against
+    /// `max_file_size`. Unix already enforces this pre-emptively via `ulimit -f` (see `apply`), but
+    /// Windows Job Objects have no equivalent file-size limit flag, so this post-hoc check is what
+    /// actually enforces `max_file_size` there.
+    pub fn run(
+        &self,
+        command: &mut Command,
+        timeout: Duration,
+        cancellation_token: &CancellationToken,
+        output_path: Option<&Path>,
+    ) -> Result<Output, CompilationUtilError> {
+        let output = {
+            #[cfg(target_family = "unix")]
+            {
+                unix::run_supervised(self, command, timeout, cancellation_token)?
+            }
+            #[cfg(target_family = "windows")]
+            {
+                windows_job::run_supervised(self, command, timeout, cancellation_token)?
+            }
+        };
+        if output.status.success() {
+            self.check_output_file_size(output_path)?;
+        }
+        Ok(output)
+    }
+
+    fn check_output_file_size(&self, output_path: Option<&Path>) -> Result<(), CompilationUtilError> {
+        let (Some(max_file_size), Some(output_path)) = (self.max_file_size, output_path) else {
+            return Ok(());
+        };
+        let size = std::fs::metadata(output_path)?.len();
+        if size > max_file_size {
+            return Err(CompilationUtilError::CompilationError(format!(
+                "Compiler output at {output_path:?} is {size} bytes, exceeding the configured \
+                 limit of {max_file_size} bytes"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of racing a child process against a deadline/cancellation token.
+#[derive(Clone, Copy)]
+enum SupervisedExit {
+    Exited,
+    TimedOut,
+    Cancelled,
+}
+
+fn wait_supervised(
+    mut try_wait: impl FnMut() -> std::io::Result<bool>,
+    timeout: Duration,
+    cancellation_token: &CancellationToken,
+) -> std::io::Result<SupervisedExit> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if try_wait()? {
+            return Ok(SupervisedExit::Exited);
+        }
+        if cancellation_token.is_cancelled() {
+            return Ok(SupervisedExit::Cancelled);
+        }
+        if Instant::now() >= deadline {
+            return Ok(SupervisedExit::TimedOut);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod wait_supervised_tests {
+    use super::*;
+
+    #[test]
+    fn exits_as_soon_as_try_wait_reports_done() {
+        let result =
+            wait_supervised(|| Ok(true), Duration::from_secs(10), &CancellationToken::new());
+        assert!(matches!(result, Ok(SupervisedExit::Exited)));
+    }
+
+    #[test]
+    fn times_out_once_deadline_passes() {
+        let result =
+            wait_supervised(|| Ok(false), Duration::from_millis(1), &CancellationToken::new());
+        assert!(matches!(result, Ok(SupervisedExit::TimedOut)));
+    }
+
+    #[test]
+    fn cancels_when_token_is_flipped_mid_poll() {
+        let cancellation_token = CancellationToken::new();
+        let token_to_flip = cancellation_token.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(POLL_INTERVAL);
+            token_to_flip.cancel();
+        });
+
+        let result = wait_supervised(|| Ok(false), Duration::from_secs(10), &cancellation_token);
+        assert!(matches!(result, Ok(SupervisedExit::Cancelled)));
+    }
+
+    #[test]
+    fn propagates_try_wait_errors() {
+        let result = wait_supervised(
+            || Err(std::io::Error::other("try_wait failed")),
+            Duration::from_secs(10),
+            &CancellationToken::new(),
+        );
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(target_family = "unix")]
+mod unix {
+    use std::os::unix::process::CommandExt;
+    use std::process::{Command, Output, Stdio};
+    use std::time::Duration;
+
+    use super::{wait_supervised, ResourceLimits, SupervisedExit};
+    use crate::cancellation::CancellationToken;
+    use crate::errors::CompilationUtilError;
+
+    pub(super) fn run_supervised(
+        limits: &ResourceLimits,
+        command: &mut Command,
+        timeout: Duration,
+        cancellation_token: &CancellationToken,
+    ) -> Result<Output, CompilationUtilError> {
+        limits.apply(command);
+        // Make the child the leader of its own process group, so a timeout/cancellation can kill
+        // the whole tree (the compiler plus anything it spawns) via a single `killpg`, rather
+        // than leaking grandchildren that `Child::kill` alone would not reach.
+        command.process_group(0);
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+        let mut child = command.spawn()?;
+        let pid = child.id() as i32;
+
+        let exit = wait_supervised(|| Ok(child.try_wait()?.is_some()), timeout, cancellation_token)?;
+        match exit {
+            SupervisedExit::Exited => Ok(child.wait_with_output()?),
+            SupervisedExit::TimedOut | SupervisedExit::Cancelled => {
+                // SAFETY: `killpg` with a process group id obtained from our own child's pid is
+                // always a valid call; it signals every process in that group.
+                unsafe {
+                    libc::killpg(pid, libc::SIGKILL);
+                }
+                let _ = child.wait();
+                Err(if matches!(exit, SupervisedExit::TimedOut) {
+                    CompilationUtilError::Timeout
+                } else {
+                    CompilationUtilError::Cancelled
+                })
+            }
+        }
+    }
+}
+
+#[cfg(target_family = "windows")]
+mod windows_job {
+    use std::mem;
+    use std::os::windows::io::AsRawHandle;
+    use std::process::{Command, Output, Stdio};
+    use std::time::Duration;
+
+    use windows_sys::Win32::Foundation::HANDLE;
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, QueryInformationJobObject,
+        SetInformationJobObject, TerminateJobObject, JobObjectExtendedLimitInformation,
+        JOBOBJECT_BASIC_ACCOUNTING_INFORMATION, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JOB_OBJECT_LIMIT_JOB_MEMORY, JOB_OBJECT_LIMIT_PROCESS_TIME,
+    };
+
+    use super::{wait_supervised, ResourceLimits, SupervisedExit};
+    use crate::cancellation::CancellationToken;
+    use crate::errors::CompilationUtilError;
+
+    /// Spawns `command`, assigns it (and, transitively, any children it spawns, which inherit
+    /// job membership by default) to a Job Object enforcing `limits`, and waits for it to exit,
+    /// a timeout, or cancellation. On timeout/cancellation, `TerminateJobObject` kills the whole
+    /// tree in one call. If the job terminates the process for exceeding a resource limit, this
+    /// surfaces a `CompilationError` instead of the generic "Process exited with non-zero status"
+    /// message.
+    pub(super) fn run_supervised(
+        limits: &ResourceLimits,
+        command: &mut Command,
+        timeout: Duration,
+        cancellation_token: &CancellationToken,
+    ) -> Result<Output, CompilationUtilError> {
+        // SAFETY: `CreateJobObjectW` with a null name and null security attributes always
+        // returns either a valid handle or NULL on failure; the handle is closed via
+        // `JobHandle`'s `Drop` impl below.
+        let job = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+        if job == 0 {
+            return Err(CompilationUtilError::UnexpectedError(
+                "Failed to create Windows Job Object for resource limits".to_owned(),
+            ));
+        }
+        let job = JobHandle(job);
+
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { mem::zeroed() };
+        if let Some(max_cpu_time) = limits.max_cpu_time {
+            // `PerProcessUserTimeLimit` is in 100-nanosecond units, and is only honored once
+            // `JOB_OBJECT_LIMIT_PROCESS_TIME` is set (`JOB_OBJECT_LIMIT_JOB_TIME` instead governs
+            // the separate, all-zeroed `PerJobUserTimeLimit`).
+            info.BasicLimitInformation.PerProcessUserTimeLimit = (max_cpu_time * 10_000_000) as i64;
+            info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_TIME;
+        }
+        if let Some(max_memory_usage) = limits.max_memory_usage {
+            info.JobMemoryLimit = max_memory_usage as usize;
+            info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_JOB_MEMORY;
+        }
+
+        // SAFETY: `info` is a valid, fully-initialized `JOBOBJECT_EXTENDED_LIMIT_INFORMATION`
+        // and `job.0` is the handle created above.
+        let set_ok = unsafe {
+            SetInformationJobObject(
+                job.0,
+                JobObjectExtendedLimitInformation,
+                &info as *const _ as *const _,
+                mem::size_of_val(&info) as u32,
+            )
+        };
+        if set_ok == 0 {
+            return Err(CompilationUtilError::UnexpectedError(
+                "Failed to configure Windows Job Object resource limits".to_owned(),
+            ));
+        }
+
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        let mut child = command.spawn()?;
+        // SAFETY: `child.as_raw_handle()` is a valid process handle for as long as `child` is
+        // alive, which outlives this call.
+        let assign_ok = unsafe { AssignProcessToJobObject(job.0, child.as_raw_handle() as HANDLE) };
+        if assign_ok == 0 {
+            // The child is already running at this point (unavoidable without `CREATE_SUSPENDED`
+            // plumbing through `std::process::Command`); best-effort kill it rather than leak it.
+            let _ = child.kill();
+            return Err(CompilationUtilError::UnexpectedError(
+                "Failed to assign compiler process to Windows Job Object".to_owned(),
+            ));
+        }
+
+        let exit = wait_supervised(|| Ok(child.try_wait()?.is_some()), timeout, cancellation_token)?;
+        match exit {
+            SupervisedExit::Exited => {
+                let output = child.wait_with_output()?;
+                if !output.status.success() {
+                    if let Some(reason) = job_termination_reason(&job) {
+                        return Err(CompilationUtilError::CompilationError(reason));
+                    }
+                }
+                Ok(output)
+            }
+            SupervisedExit::TimedOut | SupervisedExit::Cancelled => {
+                // SAFETY: `job.0` is a valid handle owned by this call.
+                unsafe {
+                    TerminateJobObject(job.0, 1);
+                }
+                let _ = child.wait();
+                Err(if matches!(exit, SupervisedExit::TimedOut) {
+                    CompilationUtilError::Timeout
+                } else {
+                    CompilationUtilError::Cancelled
+                })
+            }
+        }
+    }
+
+    /// Inspects the job's accounting info to tell whether the process was killed for exceeding
+    /// the memory or CPU limit, producing a message analogous to the unix signal-decoding path.
+    fn job_termination_reason(job: &JobHandle) -> Option<String> {
+        let mut accounting: JOBOBJECT_BASIC_ACCOUNTING_INFORMATION = unsafe { mem::zeroed() };
+        // SAFETY: `accounting` is large enough for `JobObjectBasicAccountingInformation` and
+        // `job.0` is a valid handle.
+        let ok = unsafe {
+            QueryInformationJobObject(
+                job.0,
+                windows_sys::Win32::System::JobObjects::JobObjectBasicAccountingInformation,
+                &mut accounting as *mut _ as *mut _,
+                mem::size_of_val(&accounting) as u32,
+                std::ptr::null_mut(),
+            )
+        };
+        if ok == 0 {
+            return None;
+        }
+        if accounting.TotalTerminatedProcesses > 0 {
+            Some(
+                "Job Object terminated for exceeding memory/CPU limit: process killed by the \
+                 Windows job accounting the compiler ran under."
+                    .to_owned(),
+            )
+        } else {
+            None
+        }
+    }
+
+    struct JobHandle(HANDLE);
+
+    impl Drop for JobHandle {
+        fn drop(&mut self) {
+            // SAFETY: `self.0` is a valid handle created by `CreateJobObjectW` in `run_supervised`.
+            unsafe {
+                windows_sys::Win32::Foundation::CloseHandle(self.0);
+            }
+        }
+    }
+}